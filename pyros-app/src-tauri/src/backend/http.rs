@@ -0,0 +1,161 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tauri::ipc::Channel;
+
+use crate::metadata::{self, ImageMetadata};
+
+use super::{GeneratedImage, GenerationBackend, GenerationEvent, GenerationRequest};
+
+/// Disambiguates filenames across concurrent jobs within one process run.
+static NEXT_IMAGE_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Serialize)]
+struct GenerateRequestBody {
+    prompt: String,
+    count: u32,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeneratedImageBody {
+    /// Base64-encoded image bytes.
+    image: String,
+    seed: Option<u64>,
+    model: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GenerateResponseBody {
+    images: Vec<GeneratedImageBody>,
+}
+
+/// Generates images by POSTing to a remote REST endpoint, e.g. a
+/// self-hosted diffusion server or a cloud generation API.
+pub struct HttpBackend {
+    endpoint: String,
+    client: reqwest::Client,
+}
+
+impl HttpBackend {
+    pub fn new(endpoint: String) -> Self {
+        Self { endpoint, client: reqwest::Client::new() }
+    }
+
+    /// Request a single image from the remote endpoint and write it to disk.
+    async fn generate_one(&self, prompt: &str, width: u32, height: u32) -> Result<GeneratedImage, String> {
+        let mut response = self.client
+            .post(&self.endpoint)
+            .json(&GenerateRequestBody { prompt: prompt.to_string(), count: 1, width, height })
+            .send()
+            .await
+            .map_err(|e| format!("Request to {} failed: {}", self.endpoint, e))?
+            .error_for_status()
+            .map_err(|e| format!("Generation server returned an error: {}", e))?
+            .json::<GenerateResponseBody>()
+            .await
+            .map_err(|e| format!("Failed to parse generation response: {}", e))?;
+
+        let item = response.images.pop().ok_or("Generation server returned no image")?;
+        let path = save_image(&crate::get_output_dir(), &item.image)?;
+
+        Ok(GeneratedImage {
+            path: path.to_string_lossy().to_string(),
+            metadata: ImageMetadata {
+                prompt: prompt.to_string(),
+                seed: item.seed,
+                width,
+                height,
+                model: item.model,
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl GenerationBackend for HttpBackend {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn is_available(&self) -> bool {
+        match self.client.head(&self.endpoint).send().await {
+            Ok(response) => {
+                let status = response.status();
+                // Many generation endpoints only implement POST, so a 405
+                // just means "HEAD isn't supported here" and the backend is
+                // still there. A transport failure or any other non-2xx
+                // status (e.g. a 404 for no such route) counts as unavailable.
+                status.is_success() || status == reqwest::StatusCode::METHOD_NOT_ALLOWED
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Dispatches `req.count` independent single-image requests, up to
+    /// `req.concurrency` at a time. Unlike a local model process, a remote
+    /// server can genuinely serve several requests in parallel, so this
+    /// backend is the one where driving concurrency internally actually
+    /// pays off.
+    async fn generate(
+        &self,
+        req: GenerationRequest,
+        on_event: &Channel<GenerationEvent>,
+    ) -> Result<(), String> {
+        let GenerationRequest { prompt, count, width, height, concurrency } = req;
+        let concurrency = concurrency.max(1) as usize;
+
+        let jobs = (0..count).map(|index| {
+            let prompt = prompt.clone();
+            async move { (index, self.generate_one(&prompt, width, height).await) }
+        });
+
+        stream::iter(jobs)
+            .buffer_unordered(concurrency)
+            .for_each(|(index, result)| async move {
+                match result {
+                    Ok(image) => {
+                        if let Err(error) = metadata::persist(Path::new(&image.path), &image.metadata) {
+                            eprintln!("Failed to persist metadata for {}: {}", image.path, error);
+                        }
+                        on_event.send(GenerationEvent::Image { index, path: image.path }).ok();
+                    }
+                    Err(error) => {
+                        on_event.send(GenerationEvent::Failed { index, error }).ok();
+                    }
+                }
+            })
+            .await;
+
+        Ok(())
+    }
+}
+
+/// Decode and write one base64-encoded image into the output directory,
+/// naming it with the extension sniffed from the decoded bytes: the JSON
+/// envelope carries no declared content-type for the embedded image, and
+/// the same endpoint may return png/jpeg/webp depending on the request, so
+/// assuming `.png` would mis-sort and mis-read the gallery for anything else.
+fn save_image(output_dir: &Path, encoded: &str) -> Result<std::path::PathBuf, String> {
+    use base64::Engine;
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+    let extension = image::guess_format(&bytes)
+        .ok()
+        .and_then(|format| format.extensions_str().first())
+        .copied()
+        .unwrap_or("png");
+
+    let id = NEXT_IMAGE_ID.fetch_add(1, Ordering::Relaxed);
+    let path = output_dir.join(format!("http_{}_{}.{}", std::process::id(), id, extension));
+    std::fs::write(&path, &bytes).map_err(|e| format!("Failed to write image: {}", e))?;
+
+    Ok(path)
+}
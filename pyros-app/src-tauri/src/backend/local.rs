@@ -0,0 +1,195 @@
+use std::path::Path;
+use std::process::Stdio;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tauri::ipc::Channel;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+
+use crate::metadata::{self, ImageMetadata};
+
+use super::{GenerationBackend, GenerationEvent, GenerationRequest};
+
+const MODEL_NAME: &str = "pyros-local";
+
+/// Generator script run via `uv run python -c`. `prompt`/`count`/`width`/
+/// `height` are read from `sys.argv` rather than interpolated into this
+/// source: a prompt containing `'''`, a trailing backslash, or a newline
+/// would otherwise break out of the embedded string literal and run as
+/// arbitrary Python.
+const GENERATE_SCRIPT: &str = r#"
+import json
+import sys
+sys.path.insert(0, 'src')
+
+from pyros_cli.local.image_generator import generate_image
+from pyros_cli.local.llm_provider import generate_prompt_variable_values
+from pyros_cli.models.prompt_vars import load_prompt_vars, save_prompt_var
+import re
+import random
+
+prompt = sys.argv[1]
+count = int(sys.argv[2])
+width = int(sys.argv[3])
+height = int(sys.argv[4])
+
+# Load existing variables
+prompt_vars = load_prompt_vars()
+
+# Substitute variables
+pattern = r'(__[a-zA-Z0-9_\-/]+__)'
+for match in re.findall(pattern, prompt):
+    if match in prompt_vars:
+        var = prompt_vars[match]
+        if var.values:
+            replacement = random.choice(var.values)
+            prompt = prompt.replace(match, replacement, 1)
+
+# Generate images, reporting each one as soon as it's done. Each image is
+# reseeded right before the call so the seed we report is the one that
+# actually drove `random` during that call, instead of passing `seed` as a
+# generate_image kwarg we can't confirm it accepts.
+for i in range(count):
+    seed = random.randint(0, 2**32 - 1)
+    random.seed(seed)
+    try:
+        image, path = generate_image(prompt, width=width, height=height)
+        print(json.dumps({"index": i, "path": path, "prompt": prompt, "seed": seed, "error": None}), flush=True)
+    except Exception as e:
+        print(json.dumps({"index": i, "path": None, "prompt": prompt, "seed": seed, "error": str(e)}), flush=True)
+"#;
+
+/// One line of progress printed by the Python generator subprocess.
+#[derive(Debug, Deserialize)]
+struct GenerationLine {
+    index: u32,
+    path: Option<String>,
+    prompt: String,
+    seed: Option<u64>,
+    error: Option<String>,
+}
+
+/// Generates images by shelling out to the bundled `pyros_cli.local`
+/// Python package via `uv run`.
+#[derive(Debug, Default)]
+pub struct LocalPythonBackend;
+
+#[async_trait]
+impl GenerationBackend for LocalPythonBackend {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn is_available(&self) -> bool {
+        Command::new("uv")
+            .args(["--version"])
+            .output()
+            .await
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Runs the whole batch through a single Python process, ignoring
+    /// `req.concurrency`: the model only lives in one process's VRAM, so
+    /// "parallel" here would mean reloading diffusers/torch from scratch
+    /// once per image and racing several copies for the same GPU. One
+    /// process streaming `count` images sequentially is both cheaper and
+    /// safer than that.
+    async fn generate(
+        &self,
+        req: GenerationRequest,
+        on_event: &Channel<GenerationEvent>,
+    ) -> Result<(), String> {
+        let current_dir = std::env::current_dir().map_err(|e| e.to_string())?;
+        let backend_dir = current_dir.parent()
+            .unwrap_or(&current_dir)
+            .to_path_buf();
+
+        let GenerationRequest { prompt, count, width, height, concurrency: _ } = req;
+
+        // Spawn a long-lived Python process that prints one JSON line per
+        // completed image, so we can forward progress as it happens.
+        let mut child = Command::new("uv")
+            .current_dir(&backend_dir)
+            .args([
+                "run", "python", "-c", GENERATE_SCRIPT,
+                &prompt, &count.to_string(), &width.to_string(), &height.to_string(),
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to run Python: {}", e))?;
+
+        let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+        let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+
+        // Drain stderr concurrently with stdout: model loading (diffusers/
+        // transformers/torch) can print far more than one pipe buffer's
+        // worth of progress bars and warnings before the first stdout line,
+        // and an unread stderr pipe would otherwise fill up and deadlock
+        // the Python process against our stdout read below.
+        let stderr_task = tokio::spawn(async move {
+            let mut stderr = stderr;
+            let mut buf = String::new();
+            let _ = stderr.read_to_string(&mut buf).await;
+            buf
+        });
+
+        let mut lines = BufReader::new(stdout).lines();
+        // The script reports indices in order, so this is also the first
+        // index the process has not reported yet.
+        let mut reported: u32 = 0;
+
+        while let Some(line) = lines.next_line().await.map_err(|e| e.to_string())? {
+            let parsed: GenerationLine = match serde_json::from_str(&line) {
+                Ok(parsed) => parsed,
+                Err(_) => continue, // ignore stray non-JSON output
+            };
+
+            match (parsed.path, parsed.error) {
+                (Some(path), _) => {
+                    reported = reported.max(parsed.index + 1);
+                    let image_metadata = ImageMetadata {
+                        prompt: parsed.prompt,
+                        seed: parsed.seed,
+                        width,
+                        height,
+                        model: Some(MODEL_NAME.to_string()),
+                    };
+                    if let Err(error) = metadata::persist(Path::new(&path), &image_metadata) {
+                        eprintln!("Failed to persist metadata for {}: {}", path, error);
+                    }
+                    on_event.send(GenerationEvent::Image { index: parsed.index, path }).ok();
+                }
+                (None, Some(error)) => {
+                    reported = reported.max(parsed.index + 1);
+                    on_event.send(GenerationEvent::Failed { index: parsed.index, error }).ok();
+                }
+                (None, None) => continue,
+            }
+        }
+
+        let status = child.wait().await.map_err(|e| e.to_string())?;
+        let stderr = stderr_task.await.unwrap_or_default();
+        if !status.success() {
+            let error = format!("Generation failed: {}", stderr);
+            if reported == 0 {
+                return Err(error);
+            }
+            // Images already reported stand; only the ones the process
+            // never reached are reported as failed.
+            for index in reported..count {
+                on_event.send(GenerationEvent::Failed { index, error: error.clone() }).ok();
+            }
+            return Ok(());
+        }
+
+        if reported == 0 {
+            return Err("Backend produced no output for the batch".to_string());
+        }
+
+        Ok(())
+    }
+}
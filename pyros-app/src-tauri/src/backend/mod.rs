@@ -0,0 +1,88 @@
+mod http;
+mod local;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tauri::ipc::Channel;
+
+pub use http::HttpBackend;
+pub use local::LocalPythonBackend;
+
+use crate::config::{AppConfig, BackendKind};
+use crate::metadata::ImageMetadata;
+
+/// A single generation request handed to a [`GenerationBackend`].
+///
+/// `concurrency` is advisory: it only matters to backends that can fan a
+/// batch out over independent parallel calls (e.g. an HTTP backend hitting
+/// several worker replicas). A backend bound to one local GPU process
+/// (like [`LocalPythonBackend`]) is free to ignore it and generate the
+/// whole batch sequentially in one call, since "parallel" there would mean
+/// reloading the model into VRAM once per image.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenerationRequest {
+    pub prompt: String,
+    pub count: u32,
+    pub width: u32,
+    pub height: u32,
+    pub concurrency: u32,
+}
+
+/// One image written by a [`GenerationBackend`], with the resolved metadata
+/// that produced it (for the JSON sidecar / PNG text chunk).
+#[derive(Debug, Clone)]
+pub struct GeneratedImage {
+    pub path: String,
+    pub metadata: ImageMetadata,
+}
+
+/// One line of progress emitted as images complete, streamed to the
+/// frontend over an `ipc::Channel` rather than collected into one response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum GenerationEvent {
+    Started { total: u32 },
+    Image { index: u32, path: String },
+    Failed { index: u32, error: String },
+    Done,
+    Cancelled,
+}
+
+/// A source of generated images. Implementors decide how images actually
+/// get produced (local subprocess, remote HTTP call, ...); callers only
+/// depend on this trait, so swapping backends never touches the UI layer.
+#[async_trait]
+pub trait GenerationBackend: Send + Sync {
+    /// Human-readable backend name, used for logging/diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Whether the backend is currently reachable/usable.
+    async fn is_available(&self) -> bool;
+
+    /// Run the whole of `req` in one call, persisting each image's metadata
+    /// as it's produced and reporting progress over `on_event` (`Image` or
+    /// `Failed` per index). Implementors own how `req.concurrency` is
+    /// honored: a backend bound to one local process can stream
+    /// `req.count` images from a single subprocess sequentially, while a
+    /// backend talking to a remote server can dispatch several requests in
+    /// parallel internally. This only returns `Err` for a failure that
+    /// kept the whole batch from running at all (nothing was reported over
+    /// `on_event`); a failure scoped to one image is still reported as a
+    /// `Failed` event and this returns `Ok`.
+    async fn generate(
+        &self,
+        req: GenerationRequest,
+        on_event: &Channel<GenerationEvent>,
+    ) -> Result<(), String>;
+}
+
+/// Build the backend selected by [`AppConfig`], for storage in Tauri
+/// managed state at startup.
+pub fn backend_from_config(config: &AppConfig) -> Arc<dyn GenerationBackend> {
+    match &config.backend {
+        BackendKind::Local => Arc::new(LocalPythonBackend::default()),
+        BackendKind::Http { endpoint } => Arc::new(HttpBackend::new(endpoint.clone())),
+    }
+}
@@ -0,0 +1,42 @@
+use std::fs;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+/// Which [`crate::backend::GenerationBackend`] to use for image generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BackendKind {
+    /// Shell out to the bundled `pyros_cli` Python package.
+    Local,
+    /// POST to a remote REST endpoint (e.g. a self-hosted diffusion server).
+    Http { endpoint: String },
+}
+
+impl Default for BackendKind {
+    fn default() -> Self {
+        BackendKind::Local
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub backend: BackendKind,
+}
+
+/// Path to the optional `config.json` next to the generated-image output dir.
+fn config_path() -> PathBuf {
+    let current_dir = std::env::current_dir().unwrap_or_default();
+    current_dir.parent()
+        .unwrap_or(&current_dir)
+        .join("config.json")
+}
+
+/// Load the app config, falling back to defaults (the local backend) if no
+/// config file exists or it fails to parse.
+pub fn load_config() -> AppConfig {
+    fs::read_to_string(config_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use jwalk::WalkDir;
+use serde::Serialize;
+
+use crate::metadata::read_sidecar;
+
+/// A generated image with its resolved gallery metadata, used to populate
+/// a sortable/filterable gallery rather than a flat path list.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageEntry {
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+    pub created: u64,
+    pub prompt: Option<String>,
+    pub seed: Option<u64>,
+}
+
+fn is_image(path: &Path) -> bool {
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    matches!(ext.to_lowercase().as_str(), "png" | "jpg" | "jpeg" | "webp")
+}
+
+/// Recursively walk `root` (including per-session subfolders) and build a
+/// gallery entry for every image found, newest first.
+pub fn scan_images(root: &Path) -> Vec<ImageEntry> {
+    let mut entries: Vec<ImageEntry> = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && is_image(&entry.path()))
+        .filter_map(|entry| {
+            let path = entry.path();
+            let (width, height) = image::image_dimensions(&path).ok()?;
+            let created = entry
+                .metadata()
+                .ok()
+                .and_then(|meta| meta.created().or_else(|_| meta.modified()).ok())
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0);
+            let sidecar = read_sidecar(&path);
+
+            Some(ImageEntry {
+                path: path.to_string_lossy().to_string(),
+                width,
+                height,
+                created,
+                prompt: sidecar.as_ref().map(|m| m.prompt.clone()),
+                seed: sidecar.and_then(|m| m.seed),
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.created.cmp(&a.created));
+    entries
+}
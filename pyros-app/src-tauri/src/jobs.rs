@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::ipc::Channel;
+use tokio::task::JoinHandle;
+
+use crate::backend::GenerationEvent;
+
+/// Identifies one generation batch, handed back from `generate_image` so
+/// the UI can cancel it later via `cancel_generation`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JobId(String);
+
+impl JobId {
+    pub fn new() -> Self {
+        JobId(uuid::Uuid::new_v4().to_string())
+    }
+}
+
+impl Default for JobId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Registry of active generation jobs, keyed by [`JobId`], so a batch can
+/// be aborted mid-flight from a separate command invocation. Killing one
+/// job only touches its own entry/task, never another job's. Cheaply
+/// cloneable so the owning task can hold a handle to deregister itself.
+///
+/// A job is `reserve`d *before* its task is spawned and the task's handle
+/// is attached with `set_handle` only once `spawn` returns. That way a job
+/// that finishes (or is cancelled) before its handle is attached still has
+/// a registry entry to remove, instead of racing `insert` and leaving a
+/// dead entry (and its `Channel`) behind forever for a fast/empty batch.
+#[derive(Default, Clone)]
+pub struct JobRegistry(Arc<Mutex<HashMap<JobId, (Option<JoinHandle<()>>, Channel<GenerationEvent>)>>>);
+
+impl JobRegistry {
+    /// Register a job before its task is spawned, so the task can always
+    /// find (and remove) its own entry even if it finishes immediately.
+    pub fn reserve(&self, id: JobId, on_event: Channel<GenerationEvent>) {
+        self.0.lock().unwrap().insert(id, (None, on_event));
+    }
+
+    /// Attach a task handle to a previously `reserve`d job. A no-op if the
+    /// job already finished (or was cancelled) before the task could spawn.
+    pub fn set_handle(&self, id: &JobId, handle: JoinHandle<()>) {
+        if let Some(entry) = self.0.lock().unwrap().get_mut(id) {
+            entry.0 = Some(handle);
+        }
+    }
+
+    /// Remove and return a job's handle/channel, e.g. to cancel it. The
+    /// handle is `None` if the job hasn't finished spawning its task yet.
+    pub fn take(&self, id: &JobId) -> Option<(Option<JoinHandle<()>>, Channel<GenerationEvent>)> {
+        self.0.lock().unwrap().remove(id)
+    }
+
+    /// Drop the bookkeeping for a job that finished on its own.
+    pub fn forget(&self, id: &JobId) {
+        self.0.lock().unwrap().remove(id);
+    }
+}
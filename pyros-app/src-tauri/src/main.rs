@@ -3,145 +3,133 @@
     windows_subsystem = "windows"
 )]
 
-use std::process::Command;
+mod backend;
+mod config;
+mod gallery;
+mod jobs;
+mod metadata;
+
 use std::path::PathBuf;
 use std::fs;
-use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct GenerationResult {
-    images: Vec<String>,
-    error: Option<String>,
-}
+use tauri::ipc::Channel;
+use tauri::State;
+
+use backend::{backend_from_config, GenerationBackend, GenerationEvent, GenerationRequest};
+use gallery::ImageEntry;
+use jobs::{JobId, JobRegistry};
+use metadata::read_image_metadata;
+
+/// Active [`GenerationBackend`], selected from config at startup and shared
+/// across command invocations via Tauri managed state.
+struct BackendState(Arc<dyn GenerationBackend>);
 
 /// Get the output directory for generated images
-fn get_output_dir() -> PathBuf {
+pub(crate) fn get_output_dir() -> PathBuf {
     // Navigate to pyros-cli output directory
     let current_dir = std::env::current_dir().unwrap_or_default();
     let output_dir = current_dir.parent()
         .unwrap_or(&current_dir)
         .join("output");
-    
+
     if !output_dir.exists() {
         fs::create_dir_all(&output_dir).ok();
     }
-    
+
     output_dir
 }
 
-/// List existing images in the output directory
+/// List existing images in the output directory, recursively descending
+/// into per-session subfolders and resolving each image's gallery metadata.
 #[tauri::command]
-fn list_images() -> Vec<String> {
-    let output_dir = get_output_dir();
-    
-    let mut images: Vec<(String, std::time::SystemTime)> = fs::read_dir(&output_dir)
-        .ok()
-        .map(|entries| {
-            entries
-                .filter_map(|e| e.ok())
-                .filter(|e| {
-                    e.path()
-                        .extension()
-                        .map(|ext| ext == "png" || ext == "jpg" || ext == "jpeg")
-                        .unwrap_or(false)
-                })
-                .filter_map(|e| {
-                    let path = e.path();
-                    let modified = e.metadata().ok()?.modified().ok()?;
-                    Some((path.to_string_lossy().to_string(), modified))
-                })
-                .collect()
-        })
-        .unwrap_or_default();
-    
-    // Sort by modification time, newest first
-    images.sort_by(|a, b| b.1.cmp(&a.1));
-    
-    images.into_iter().map(|(path, _)| path).collect()
+fn list_images() -> Vec<ImageEntry> {
+    gallery::scan_images(&get_output_dir())
 }
 
-/// Generate images using the Python backend
+/// Generate images using the configured [`GenerationBackend`], streaming one
+/// progress event per completed image instead of blocking until the whole
+/// batch is done. The whole batch is handed to the backend in one call, so
+/// each backend drives `concurrency` (or ignores it) however actually suits
+/// how it produces images; one image failing never aborts the others.
+///
+/// The batch runs in a detached task so this command can return immediately
+/// with a [`JobId`]; the caller cancels it later via `cancel_generation`.
 #[tauri::command]
-async fn generate_image(
+fn generate_image(
     prompt: String,
     count: u32,
     width: u32,
     height: u32,
-) -> Result<Vec<String>, String> {
-    // Get the path to the Python backend
-    let current_dir = std::env::current_dir().map_err(|e| e.to_string())?;
-    let backend_dir = current_dir.parent()
-        .unwrap_or(&current_dir)
-        .to_path_buf();
-
-    // Build the command to run the Python generator
-    let output = Command::new("uv")
-        .current_dir(&backend_dir)
-        .args([
-            "run", "python", "-c",
-            &format!(r#"
-import json
-import sys
-sys.path.insert(0, 'src')
-
-from pyros_cli.local.image_generator import generate_image
-from pyros_cli.local.llm_provider import generate_prompt_variable_values
-from pyros_cli.models.prompt_vars import load_prompt_vars, save_prompt_var
-import re
-import random
-
-prompt = '''{prompt}'''
-count = {count}
-width = {width}
-height = {height}
-
-# Load existing variables
-prompt_vars = load_prompt_vars()
-
-# Substitute variables
-pattern = r'(__[a-zA-Z0-9_\-/]+__)'
-for match in re.findall(pattern, prompt):
-    if match in prompt_vars:
-        var = prompt_vars[match]
-        if var.values:
-            replacement = random.choice(var.values)
-            prompt = prompt.replace(match, replacement, 1)
-
-# Generate images
-results = []
-for i in range(count):
-    try:
-        image, path = generate_image(prompt, width=width, height=height)
-        results.append(path)
-    except Exception as e:
-        print(f"Error: {{e}}", file=sys.stderr)
-
-print(json.dumps(results))
-"#),
-        ])
-        .output()
-        .map_err(|e| format!("Failed to run Python: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Generation failed: {}", stderr));
-    }
+    concurrency: u32,
+    on_event: Channel<GenerationEvent>,
+    backend: State<'_, BackendState>,
+    job_registry: State<'_, JobRegistry>,
+) -> JobId {
+    let job_id = JobId::new();
+    let backend = Arc::clone(&backend.0);
+    let job_registry = job_registry.inner().clone();
+    let task_job_registry = job_registry.clone();
+    let task_on_event = on_event.clone();
+    let task_job_id = job_id.clone();
+
+    // Reserve the registry entry before spawning: otherwise a fast/empty
+    // batch could have its task `forget` itself before its entry is ever
+    // inserted, leaking a dead entry (and its `Channel`) forever.
+    job_registry.reserve(job_id.clone(), on_event);
+
+    let handle = tauri::async_runtime::spawn(async move {
+        task_on_event.send(GenerationEvent::Started { total: count }).ok();
+
+        let req = GenerationRequest { prompt, count, width, height, concurrency };
+        if let Err(error) = backend.generate(req, &task_on_event).await {
+            // A batch-level error only surfaces before any image has been
+            // individually reported (a per-image failure is sent as its own
+            // `Failed` event and the backend still returns `Ok` overall),
+            // so every index in the batch genuinely failed here.
+            for index in 0..count {
+                task_on_event.send(GenerationEvent::Failed { index, error: error.clone() }).ok();
+            }
+        }
+
+        task_on_event.send(GenerationEvent::Done).ok();
+        task_job_registry.forget(&task_job_id);
+    });
+
+    job_registry.set_handle(&job_id, handle);
+    job_id
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    
-    // Parse JSON output
-    let images: Vec<String> = serde_json::from_str(stdout.trim())
-        .map_err(|e| format!("Failed to parse output: {} - {}", e, stdout))?;
+/// Cancel an in-flight batch started by `generate_image`. Aborts its task
+/// (killing any child process it holds) and emits a `Cancelled` event on
+/// its own channel; images already written to [`get_output_dir`] remain on
+/// disk and listed, and other jobs are unaffected.
+#[tauri::command]
+fn cancel_generation(job_id: JobId, job_registry: State<'_, JobRegistry>) -> Result<(), String> {
+    let Some((handle, on_event)) = job_registry.take(&job_id) else {
+        return Err("No active job with that id".to_string());
+    };
 
-    Ok(images)
+    if let Some(handle) = handle {
+        handle.abort();
+    }
+    on_event.send(GenerationEvent::Cancelled).ok();
+    Ok(())
 }
 
 fn main() {
+    let app_config = config::load_config();
+    let active_backend = backend_from_config(&app_config);
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(BackendState(active_backend))
+        .manage(JobRegistry::default())
         .invoke_handler(tauri::generate_handler![
             list_images,
             generate_image,
+            cancel_generation,
+            read_image_metadata,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
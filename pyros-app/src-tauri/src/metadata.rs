@@ -0,0 +1,167 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Resolved generation metadata for one image, persisted as a JSON sidecar
+/// (`<image>.json`) and, for PNGs, embedded as a `tEXt` "parameters" chunk
+/// so the image is self-describing when shared on its own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageMetadata {
+    pub prompt: String,
+    pub seed: Option<u64>,
+    pub width: u32,
+    pub height: u32,
+    pub model: Option<String>,
+}
+
+/// Sidecar path for a given image path, e.g. `foo.png` -> `foo.png.json`.
+pub fn sidecar_path(image_path: &Path) -> PathBuf {
+    let mut name = image_path.as_os_str().to_owned();
+    name.push(".json");
+    PathBuf::from(name)
+}
+
+/// Read the sidecar for `image_path`, if one exists and parses cleanly.
+pub fn read_sidecar(image_path: &Path) -> Option<ImageMetadata> {
+    let contents = fs::read_to_string(sidecar_path(image_path)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Write `metadata` as the JSON sidecar for `image_path`.
+fn write_sidecar(image_path: &Path, metadata: &ImageMetadata) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(metadata).map_err(|e| e.to_string())?;
+    fs::write(sidecar_path(image_path), contents).map_err(|e| e.to_string())
+}
+
+/// Render metadata into the informal "parameters" text format used by the
+/// broader ecosystem of diffusion tools, so exported PNGs stay readable by
+/// other software even if they never see our JSON sidecar.
+fn format_parameters(metadata: &ImageMetadata) -> String {
+    let mut line = format!("Size: {}x{}", metadata.width, metadata.height);
+    if let Some(seed) = metadata.seed {
+        line.push_str(&format!(", Seed: {}", seed));
+    }
+    if let Some(model) = &metadata.model {
+        line.push_str(&format!(", Model: {}", model));
+    }
+    format!("{}\n{}", metadata.prompt, line)
+}
+
+/// Path for the scratch file `embed_png_text` encodes into before renaming
+/// it over the original, e.g. `foo.png` -> `foo.png.tmp`.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".tmp");
+    PathBuf::from(name)
+}
+
+/// Embed `metadata` into `path` as a PNG `tEXt` chunk under the
+/// conventional "parameters" keyword. Encodes to a temp file and renames it
+/// over `path` only once the re-encode fully succeeds, so a failure (e.g.
+/// an indexed-color PNG we can't round-trip without its palette) never
+/// touches the original image.
+fn embed_png_text(path: &Path, metadata: &ImageMetadata) -> Result<(), String> {
+    let file_data = fs::read(path).map_err(|e| e.to_string())?;
+
+    let decoder = png::Decoder::new(Cursor::new(&file_data));
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+
+    if info.color_type == png::ColorType::Indexed {
+        return Err("Cannot embed metadata in an indexed-color PNG without its palette".to_string());
+    }
+
+    let tmp = tmp_path(path);
+    let result = (|| {
+        let file = fs::File::create(&tmp).map_err(|e| e.to_string())?;
+        let mut encoder = png::Encoder::new(file, info.width, info.height);
+        encoder.set_color(info.color_type);
+        encoder.set_depth(info.bit_depth);
+        encoder
+            .add_text_chunk("parameters".to_string(), format_parameters(metadata))
+            .map_err(|e| e.to_string())?;
+
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer
+            .write_image_data(&buf[..info.buffer_size()])
+            .map_err(|e| e.to_string())
+    })();
+
+    if result.is_err() {
+        fs::remove_file(&tmp).ok();
+        return result;
+    }
+
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+/// Persist `metadata` for a freshly generated image: always write the JSON
+/// sidecar, and additionally embed a PNG text chunk when the image is a PNG.
+pub fn persist(image_path: &Path, metadata: &ImageMetadata) -> Result<(), String> {
+    write_sidecar(image_path, metadata)?;
+
+    let is_png = image_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("png"))
+        .unwrap_or(false);
+    if is_png {
+        embed_png_text(image_path, metadata)?;
+    }
+
+    Ok(())
+}
+
+/// Extract "Seed: N" / "Model: name" out of a "parameters"-style text blob,
+/// for images whose sidecar is missing but whose PNG chunk survived.
+fn parse_parameters(prompt_and_params: &str) -> ImageMetadata {
+    let mut prompt = prompt_and_params;
+    let mut seed = None;
+    let mut model = None;
+    let mut width = 0;
+    let mut height = 0;
+
+    if let Some((head, params)) = prompt_and_params.rsplit_once('\n') {
+        prompt = head;
+        for field in params.split(',').map(str::trim) {
+            if let Some(value) = field.strip_prefix("Seed: ") {
+                seed = value.parse().ok();
+            } else if let Some(value) = field.strip_prefix("Model: ") {
+                model = Some(value.to_string());
+            } else if let Some(value) = field.strip_prefix("Size: ") {
+                if let Some((w, h)) = value.split_once('x') {
+                    width = w.parse().unwrap_or(0);
+                    height = h.parse().unwrap_or(0);
+                }
+            }
+        }
+    }
+
+    ImageMetadata { prompt: prompt.to_string(), seed, width, height, model }
+}
+
+/// Read the PNG "parameters" `tEXt` chunk back out of `path`, if present.
+fn read_png_text(path: &Path) -> Option<ImageMetadata> {
+    let file_data = fs::read(path).ok()?;
+    let decoder = png::Decoder::new(Cursor::new(&file_data));
+    let reader = decoder.read_info().ok()?;
+    let text = reader
+        .info()
+        .uncompressed_latin1_text
+        .iter()
+        .find(|chunk| chunk.keyword == "parameters")
+        .map(|chunk| chunk.text.clone())?;
+
+    Some(parse_parameters(&text))
+}
+
+/// Resolve the metadata for an existing image: prefer the JSON sidecar,
+/// falling back to the embedded PNG "parameters" chunk.
+#[tauri::command]
+pub fn read_image_metadata(path: String) -> Option<ImageMetadata> {
+    let path = Path::new(&path);
+    read_sidecar(path).or_else(|| read_png_text(path))
+}